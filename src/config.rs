@@ -0,0 +1,84 @@
+/* This file is part of Combodate: <https://github.com/christopherphan/combodate_rust>
+ *
+ * Copyright 2023 Christopher Phan <cphan@chrisphan.com>
+ *
+ * Available under an MIT license. See LICENSE.TXT in repository root directory for more
+ * information.
+ */
+
+//! User-configurable output rows: `Label=strftime-format` pairs supplied on the
+//! command line (repeatable `--row`) or read from a config file, validated up front so a
+//! bad format specifier produces a clear error instead of a panic at render time.
+
+use chrono::format::{Item, StrftimeItems};
+use std::fs;
+
+/// A single user-defined row: a display label paired with a strftime format string.
+pub type RowSpec = (String, String);
+
+/// Splits a `--row` argument of the form `Label=%Y-%m-%d` into its label and format
+/// string, validating the format along the way.
+pub fn parse_row_arg(s: &str) -> Result<RowSpec, String> {
+    let (label, format) = s
+        .split_once('=')
+        .ok_or_else(|| format!("--row {:?} is missing a '=' separating label and format", s))?;
+    validate_format(format)?;
+    Ok((label.to_string(), format.to_string()))
+}
+
+/// Reads a config file of `Label=%Y-%m-%d`-style lines (blank lines and lines starting
+/// with `#` are ignored), validating each row along the way.
+pub fn load_config_file(path: &str) -> Result<Vec<RowSpec>, String> {
+    let contents =
+        fs::read_to_string(path).map_err(|e| format!("could not read config file {:?}: {}", path, e))?;
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(parse_row_arg)
+        .collect()
+}
+
+/// Validates a strftime format string up front by checking for an `Item::Error` among
+/// the items `chrono::format::StrftimeItems` produces for it.
+pub fn validate_format(format: &str) -> Result<(), String> {
+    for item in StrftimeItems::new(format) {
+        if item == Item::Error {
+            return Err(format!("{:?} contains an invalid format specifier", format));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_row_arg_valid_test() {
+        assert_eq!(
+            parse_row_arg("Custom=%Y-%m-%d").unwrap(),
+            (String::from("Custom"), String::from("%Y-%m-%d"))
+        );
+    }
+
+    #[test]
+    fn parse_row_arg_missing_equals_test() {
+        assert!(parse_row_arg("Custom %Y-%m-%d").is_err());
+    }
+
+    #[test]
+    fn parse_row_arg_invalid_format_test() {
+        assert!(parse_row_arg("Custom=%Q").is_err());
+    }
+
+    #[test]
+    fn validate_format_valid_test() {
+        assert!(validate_format("%Y-%m-%dT%H:%M:%S").is_ok());
+    }
+
+    #[test]
+    fn validate_format_invalid_test() {
+        assert!(validate_format("%Q").is_err());
+    }
+}
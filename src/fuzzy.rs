@@ -0,0 +1,213 @@
+/* This file is part of Combodate: <https://github.com/christopherphan/combodate_rust>
+ *
+ * Copyright 2023 Christopher Phan <cphan@chrisphan.com>
+ *
+ * Available under an MIT license. See LICENSE.TXT in repository root directory for more
+ * information.
+ */
+
+//! Best-effort parsing of loosely-formatted, human-written datetime strings such as
+//! `9 Nov 1989 22:45 +0100`, `1989-11-09`, or `Nov 9 1989`, without the caller having to
+//! specify an explicit format string.
+
+use chrono::{DateTime, FixedOffset, Local, Offset, TimeZone};
+
+const MONTH_NAMES: [(&str, u32); 12] = [
+    ("jan", 1),
+    ("feb", 2),
+    ("mar", 3),
+    ("apr", 4),
+    ("may", 5),
+    ("jun", 6),
+    ("jul", 7),
+    ("aug", 8),
+    ("sep", 9),
+    ("oct", 10),
+    ("nov", 11),
+    ("dec", 12),
+];
+
+/// Attempts to recognize a year, month, day, optional `HH:MM[:SS]` time, and optional
+/// `±HHMM`/`±HH:MM`/`Z` offset among the whitespace- and comma-separated tokens of
+/// `s`, filling in midnight and the local offset when the time or offset are absent.
+/// Returns a descriptive error naming the components that could not be found when the
+/// year, month, or day are missing.
+pub fn parse_fuzzy_datetime(s: &str) -> Result<DateTime<FixedOffset>, String> {
+    let tokens: Vec<&str> = s
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    let mut year: Option<i32> = None;
+    let mut month: Option<u32> = None;
+    let mut day: Option<u32> = None;
+    let mut time: Option<(u32, u32, u32)> = None;
+    let mut offset: Option<i32> = None;
+
+    for token in &tokens {
+        if let Some((h, m, sec)) = parse_time_token(token) {
+            time = Some((h, m, sec));
+            continue;
+        }
+        if let Some(off) = parse_offset_token(token) {
+            offset = Some(off);
+            continue;
+        }
+        if let Some(m) = month_from_name(token) {
+            month = Some(m);
+            continue;
+        }
+        if let Some((y, mo, d)) = parse_numeric_date_token(token) {
+            year = Some(y);
+            month = Some(mo);
+            day = Some(d);
+            continue;
+        }
+        if let Ok(n) = token.trim_matches(|c: char| !c.is_ascii_digit()).parse::<i32>() {
+            if token.len() == 4 && year.is_none() {
+                year = Some(n);
+            } else if (1..=31).contains(&n) && day.is_none() {
+                day = Some(n as u32);
+            } else if year.is_none() {
+                year = Some(n);
+            }
+        }
+    }
+
+    let mut missing = Vec::new();
+    if year.is_none() {
+        missing.push("year");
+    }
+    if month.is_none() {
+        missing.push("month");
+    }
+    if day.is_none() {
+        missing.push("day");
+    }
+    if !missing.is_empty() {
+        return Err(format!(
+            "could not find the following component(s) in {:?}: {}",
+            s,
+            missing.join(", ")
+        ));
+    }
+
+    let (hour, minute, second) = time.unwrap_or((0, 0, 0));
+    let offset_secs = offset.unwrap_or_else(|| Local::now().offset().fix().local_minus_utc());
+
+    let tz = FixedOffset::east_opt(offset_secs).ok_or_else(|| format!("invalid offset in {:?}", s))?;
+    tz.with_ymd_and_hms(year.unwrap(), month.unwrap(), day.unwrap(), hour, minute, second)
+        .single()
+        .ok_or_else(|| format!("{:?} is not a valid date and time", s))
+}
+
+/// Parses a `YYYY-MM-DD` (or `YYYY/MM/DD`) token, returning `(year, month, day)`.
+fn parse_numeric_date_token(token: &str) -> Option<(i32, u32, u32)> {
+    let parts: Vec<&str> = token.split(['-', '/']).collect();
+    if parts.len() != 3 {
+        return None;
+    }
+    let year: i32 = parts[0].parse().ok()?;
+    let month: u32 = parts[1].parse().ok()?;
+    let day: u32 = parts[2].parse().ok()?;
+    if parts[0].len() == 4 {
+        Some((year, month, day))
+    } else {
+        None
+    }
+}
+
+/// Parses an `HH:MM[:SS]` token.
+fn parse_time_token(token: &str) -> Option<(u32, u32, u32)> {
+    if !token.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    let parts: Vec<&str> = token.split(':').collect();
+    if parts.len() < 2 || parts.len() > 3 {
+        return None;
+    }
+    let hour: u32 = parts[0].parse().ok()?;
+    let minute: u32 = parts[1].parse().ok()?;
+    let second: u32 = if parts.len() == 3 {
+        parts[2].parse().ok()?
+    } else {
+        0
+    };
+    if hour < 24 && minute < 60 && second < 60 {
+        Some((hour, minute, second))
+    } else {
+        None
+    }
+}
+
+/// Parses a `±HHMM`, `±HH:MM`, or `Z` offset token into a signed number of seconds.
+fn parse_offset_token(token: &str) -> Option<i32> {
+    if token == "Z" || token == "z" {
+        return Some(0);
+    }
+    let mut chars = token.chars();
+    let sign = match chars.next()? {
+        '+' => 1,
+        '-' => -1,
+        _ => return None,
+    };
+    let rest: String = chars.filter(|c| *c != ':').collect();
+    if rest.len() != 4 || !rest.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let hours: i32 = rest[0..2].parse().ok()?;
+    let minutes: i32 = rest[2..4].parse().ok()?;
+    Some(sign * (hours * 3600 + minutes * 60))
+}
+
+/// Maps an English short or long month name (case-insensitive) to its 1-indexed number.
+fn month_from_name(token: &str) -> Option<u32> {
+    let lower = token.to_lowercase();
+    MONTH_NAMES
+        .iter()
+        .find(|(name, _)| lower.starts_with(name))
+        .map(|(_, n)| *n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Datelike, Timelike};
+
+    #[test]
+    fn fuzzy_test_offset_and_time() {
+        let t = parse_fuzzy_datetime("9 Nov 1989 22:45 +0100").unwrap();
+        assert_eq!(t.year(), 1989);
+        assert_eq!(t.month(), 11);
+        assert_eq!(t.day(), 9);
+        assert_eq!(t.hour(), 22);
+        assert_eq!(t.minute(), 45);
+        assert_eq!(t.offset().local_minus_utc(), 3600);
+    }
+
+    #[test]
+    fn fuzzy_test_iso_date_only() {
+        let t = parse_fuzzy_datetime("1989-11-09").unwrap();
+        assert_eq!(t.year(), 1989);
+        assert_eq!(t.month(), 11);
+        assert_eq!(t.day(), 9);
+        assert_eq!(t.hour(), 0);
+        assert_eq!(t.minute(), 0);
+    }
+
+    #[test]
+    fn fuzzy_test_month_name_first() {
+        let t = parse_fuzzy_datetime("Nov 9 1989").unwrap();
+        assert_eq!(t.year(), 1989);
+        assert_eq!(t.month(), 11);
+        assert_eq!(t.day(), 9);
+    }
+
+    #[test]
+    fn fuzzy_test_missing_components() {
+        let err = parse_fuzzy_datetime("22:45 +0100").unwrap_err();
+        assert!(err.contains("year"));
+        assert!(err.contains("month"));
+        assert!(err.contains("day"));
+    }
+}
@@ -6,19 +6,172 @@
  * information.
  */
 
-use chrono::{DateTime, Datelike, Local, SecondsFormat, TimeZone, Timelike, Utc};
+use chrono::{
+    DateTime, Datelike, Local, LocalResult, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone,
+    Timelike, Utc,
+};
+use pure_rust_locales::{locale_match, Locale};
 
+use std::env;
 use std::fmt::Display;
+use std::process::exit;
+use std::str::FromStr;
+
+mod config;
+mod fuzzy;
 
 pub fn run() {
-    let t = Local::now();
-    print!("{}", make_combodate_table(t));
+    let args: Vec<String> = env::args().skip(1).collect();
+    let parsed = parse_args(&args);
+    let t = match parsed.datetime {
+        Some(Ok(t)) => t,
+        Some(Err(errors)) => {
+            for e in &errors {
+                eprintln!("{}", e);
+            }
+            exit(1);
+        }
+        None => Local::now(),
+    };
+    let custom_rows = match parsed.custom_rows {
+        Ok(rows) => rows,
+        Err(e) => {
+            eprintln!("{}", e);
+            exit(1);
+        }
+    };
+    print!(
+        "{}",
+        make_combodate_table(t, parsed.locale, &custom_rows)
+    );
+}
+
+/// The result of reading the command-line arguments: an optional datetime parse result
+/// (`None` when the caller should fall back to `Local::now()`), the locale to render the
+/// table in, and the user-defined extra rows (or the first error encountered while
+/// parsing/validating `--row`/`--config`).
+struct ParsedArgs {
+    datetime: Option<Result<DateTime<Local>, Vec<String>>>,
+    locale: Locale,
+    custom_rows: Result<Vec<config::RowSpec>, String>,
+}
+
+/// Reads the command-line arguments looking for a datetime string, an optional
+/// `--format` strftime string, an optional `--locale` name, any number of repeated
+/// `--row Label=format` entries, and an optional `--config` file of the same rows.
+fn parse_args(args: &[String]) -> ParsedArgs {
+    let mut datetime_arg: Option<&str> = None;
+    let mut format_arg: Option<&str> = None;
+    let mut locale = Locale::POSIX;
+    let mut custom_rows: Result<Vec<config::RowSpec>, String> = Ok(Vec::new());
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--format" => {
+                i += 1;
+                format_arg = args.get(i).map(|s| &s[..]);
+            }
+            "--locale" => {
+                i += 1;
+                if let Some(name) = args.get(i) {
+                    locale = Locale::from_str(name).unwrap_or(Locale::POSIX);
+                }
+            }
+            "--row" => {
+                i += 1;
+                if let (Ok(rows), Some(spec)) = (&mut custom_rows, args.get(i)) {
+                    match config::parse_row_arg(spec) {
+                        Ok(row) => rows.push(row),
+                        Err(e) => custom_rows = Err(e),
+                    }
+                }
+            }
+            "--config" => {
+                i += 1;
+                if let (Ok(rows), Some(path)) = (&mut custom_rows, args.get(i)) {
+                    match config::load_config_file(path) {
+                        Ok(extra) => rows.extend(extra),
+                        Err(e) => custom_rows = Err(e),
+                    }
+                }
+            }
+            other => datetime_arg = Some(other),
+        }
+        i += 1;
+    }
+    ParsedArgs {
+        datetime: datetime_arg.map(|s| parse_datetime(s, format_arg)),
+        locale,
+        custom_rows,
+    }
+}
+
+/// Attempts to parse `s` as a datetime by trying, in order, RFC 3339, RFC 2822, a bare
+/// Unix timestamp, (if given) a user-supplied strftime format, and finally the fuzzy
+/// parser in `fuzzy::parse_fuzzy_datetime`. Returns the first
+/// successful parse converted to `Local`, or the list of errors from every attempt.
+fn parse_datetime(s: &str, format: Option<&str>) -> Result<DateTime<Local>, Vec<String>> {
+    let mut errors = Vec::new();
+
+    match DateTime::parse_from_rfc3339(s) {
+        Ok(dt) => return Ok(dt.with_timezone(&Local)),
+        Err(e) => errors.push(format!("RFC 3339: {}", e)),
+    }
+
+    match DateTime::parse_from_rfc2822(s) {
+        Ok(dt) => return Ok(dt.with_timezone(&Local)),
+        Err(e) => errors.push(format!("RFC 2822: {}", e)),
+    }
+
+    match s.parse::<i64>() {
+        Ok(ts) => match Local.timestamp_opt(ts, 0) {
+            LocalResult::Single(dt) => return Ok(dt),
+            _ => errors.push(String::from("Unix timestamp: out of range or ambiguous")),
+        },
+        Err(e) => errors.push(format!("Unix timestamp: {}", e)),
+    }
+
+    if let Some(fmt) = format {
+        match parse_with_format(s, fmt) {
+            Ok(dt) => return Ok(dt),
+            Err(e) => errors.push(format!("--format {:?}: {}", fmt, e)),
+        }
+    }
+
+    match fuzzy::parse_fuzzy_datetime(s) {
+        Ok(dt) => return Ok(dt.with_timezone(&Local)),
+        Err(e) => errors.push(format!("fuzzy: {}", e)),
+    }
+
+    Err(errors)
 }
 
-fn make_combodate_table(t: DateTime<Local>) -> String {
+/// Parses `s` against the user-supplied strftime `fmt`, which carries no timezone
+/// offset: tries a full `NaiveDateTime` first, falling back to a date-only
+/// `NaiveDate` at midnight, and attaches the `Local` timezone to the result.
+fn parse_with_format(s: &str, fmt: &str) -> Result<DateTime<Local>, String> {
+    let naive = match NaiveDateTime::parse_from_str(s, fmt) {
+        Ok(ndt) => ndt,
+        Err(e) => match NaiveDate::parse_from_str(s, fmt) {
+            Ok(nd) => nd.and_hms_opt(0, 0, 0).unwrap(),
+            Err(_) => return Err(e.to_string()),
+        },
+    };
+    match Local.from_local_datetime(&naive) {
+        LocalResult::Single(dt) => Ok(dt),
+        LocalResult::Ambiguous(dt, _) => Ok(dt),
+        LocalResult::None => Err(String::from("local time does not exist (DST gap)")),
+    }
+}
+
+fn make_combodate_table(t: DateTime<Local>, locale: Locale, custom_rows: &[config::RowSpec]) -> String {
     let tu = t.with_timezone(&Utc);
+    let custom_row_values: Vec<(String, String)> = custom_rows
+        .iter()
+        .map(|(label, format)| (label.clone(), t.format(format).to_string()))
+        .collect();
     let rows = [
-        ("Unix", &unix_time(t)[..]),
+        ("Unix", &unix_time(t, locale_thousands_sep(locale))[..]),
         (
             "ISO-8601 Gregorian (Local)",
             &(t.to_rfc3339_opts(SecondsFormat::Secs, false)),
@@ -29,6 +182,8 @@ fn make_combodate_table(t: DateTime<Local>) -> String {
         ),
         ("ISO-8601 Week-date (Local)", &isoweekday(t)[..]),
         ("ISO-8601 Week-date (UTC)", &isoweekday(tu)[..]),
+        ("RFC 2822 (Local)", &(t.to_rfc2822())),
+        ("RFC 2822 (UTC)", &(tu.to_rfc2822())),
         ("Proportion of day elapsed (Local)", &proportion_day(t)[..]),
         (
             "Proportion of week elapsed (Local)",
@@ -42,8 +197,39 @@ fn make_combodate_table(t: DateTime<Local>) -> String {
             "Proportion of year elapsed (Local)",
             &proportion_year(t)[..],
         ),
+        ("Weekday name (Local)", &locale_weekday_name(t, locale)[..]),
+        ("Month name (Local)", &locale_month_name(t, locale)[..]),
+        ("Day of week (Doomsday)", &weekday_name(t)[..]),
     ];
-    make_table(&rows)
+    let mut all_rows: Vec<(&str, &str)> = rows.to_vec();
+    for (label, value) in &custom_row_values {
+        all_rows.push((label.as_str(), value.as_str()));
+    }
+    make_table(&all_rows)
+}
+
+/// Returns the thousands/grouping separator to use when rendering the Unix timestamp
+/// row: the locale's own numeric grouping separator, or the historical `' '` for the
+/// default `Locale::POSIX` so existing output is unchanged when `--locale` is absent.
+fn locale_thousands_sep(locale: Locale) -> char {
+    if locale == Locale::POSIX {
+        ' '
+    } else {
+        locale_match!(locale => LC_NUMERIC::THOUSANDS_SEP)
+            .chars()
+            .next()
+            .unwrap_or(' ')
+    }
+}
+
+/// Renders the localized long weekday name for `x` using the locale's `LC_TIME` table.
+fn locale_weekday_name<Tz: TimeZone>(x: DateTime<Tz>, locale: Locale) -> String {
+    locale_match!(locale => LC_TIME::DAY)[x.weekday().num_days_from_sunday() as usize].to_string()
+}
+
+/// Renders the localized long month name for `x` using the locale's `LC_TIME` table.
+fn locale_month_name<Tz: TimeZone>(x: DateTime<Tz>, locale: Locale) -> String {
+    locale_match!(locale => LC_TIME::MON)[x.month0() as usize].to_string()
 }
 
 fn pad(x: &str, k: usize, left: bool, pad_char: char) -> String {
@@ -121,9 +307,9 @@ where
     x.format("%G-W%V-%uT%H:%M:%S%:z").to_string()
 }
 
-fn unix_time<Tz: TimeZone>(x: DateTime<Tz>) -> String {
+fn unix_time<Tz: TimeZone>(x: DateTime<Tz>, sep: char) -> String {
     let s = format!("{}", x.timestamp());
-    separate(&s, 3, ' ')
+    separate(&s, 3, sep)
 }
 
 fn proportion_day<Tz: TimeZone>(x: DateTime<Tz>) -> String {
@@ -160,6 +346,61 @@ fn is_leap_year(year: i32) -> bool {
     year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
 }
 
+const WEEKDAY_NAMES: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+
+/// Computes the year's Doomsday rule anchor weekday (`0` = Sunday .. `6` = Saturday) as
+/// `(Tuesday + year + floor(year/4) - floor(year/100) + floor(year/400)) mod 7`.
+fn doomsday_anchor(year: i32) -> i32 {
+    (2 + year + year.div_euclid(4) - year.div_euclid(100) + year.div_euclid(400)).rem_euclid(7)
+}
+
+/// Returns a date in `month` that shares the year's Doomsday weekday: the last day of
+/// February, or one of 4/4, 6/6, 8/8, 10/10, 12/12, 5/9, 9/5, 7/11, 11/7, with January
+/// and March handled by their usual Doomsday mnemonics.
+fn doomsday_month_anchor(year: i32, month: u32) -> u32 {
+    match month {
+        1 => {
+            if is_leap_year(year) {
+                4
+            } else {
+                3
+            }
+        }
+        2 => month_length(year, 2),
+        3 => 7,
+        4 => 4,
+        5 => 9,
+        6 => 6,
+        7 => 11,
+        8 => 8,
+        9 => 5,
+        10 => 10,
+        11 => 7,
+        12 => 12,
+        _ => panic!("Invalid month"),
+    }
+}
+
+/// Computes the weekday (`0` = Sunday .. `6` = Saturday) of `year`-`month`-`day` via the
+/// Doomsday rule, independently of `chrono`.
+fn doomsday_weekday(year: i32, month: u32, day: u32) -> usize {
+    let anchor = doomsday_anchor(year);
+    let month_anchor = doomsday_month_anchor(year, month) as i32;
+    (anchor + (day as i32 - month_anchor)).rem_euclid(7) as usize
+}
+
+fn weekday_name<Tz: TimeZone>(x: DateTime<Tz>) -> String {
+    WEEKDAY_NAMES[doomsday_weekday(x.year(), x.month(), x.day())].to_string()
+}
+
 fn proportion_month<Tz: TimeZone>(x: DateTime<Tz>) -> String {
     let s = x.naive_local().num_seconds_from_midnight() + x.day0() * 86_400;
     let p = ((s as u128) * 100_000) / ((month_length(x.year(), x.month()) as u128) * 86_400);
@@ -207,13 +448,13 @@ mod tests {
     #[test]
     fn unix_test_1() {
         let x = Local.timestamp_opt(1679866623, 0).unwrap(); // is a valid Unix time
-        assert_eq!(unix_time(x), "1 679 866 623");
+        assert_eq!(unix_time(x, ' '), "1 679 866 623");
     }
 
     #[test]
     fn unix_test_2() {
         let x = Utc.timestamp_opt(626651100, 0).unwrap(); // is a valid Unix time
-        assert_eq!(unix_time(x), "626 651 100");
+        assert_eq!(unix_time(x, ' '), "626 651 100");
     }
 
     #[test]
@@ -280,6 +521,29 @@ spaces       !
         );
     }
 
+    #[test]
+    fn rfc2822_test_1() {
+        let test_date = FixedOffset::east_opt(3600)
+            .unwrap()
+            .with_ymd_and_hms(1989, 11, 9, 22, 45, 0)
+            .unwrap();
+
+        assert_eq!(
+            test_date.to_rfc2822(),
+            String::from("Thu, 9 Nov 1989 22:45:00 +0100")
+        );
+    }
+
+    #[test]
+    fn rfc2822_test_2() {
+        let test_date = Utc.with_ymd_and_hms(1989, 11, 9, 21, 45, 0).unwrap();
+
+        assert_eq!(
+            test_date.to_rfc2822(),
+            String::from("Thu, 9 Nov 1989 21:45:00 +0000")
+        );
+    }
+
     #[test]
     fn reverse_str_test_1() {
         assert_eq!(reverse_str("Koszulity"), String::from("ytiluzsoK"));
@@ -446,4 +710,123 @@ spaces       !
         let test_date = Utc.with_ymd_and_hms(2000, 4, 1, 12, 0, 0).unwrap();
         assert_eq!(proportion_year(test_date), "0.250 00");
     }
+
+    #[test]
+    fn parse_datetime_rfc3339_test() {
+        let t = parse_datetime("1989-11-09T22:45:00+01:00", None).unwrap();
+        assert_eq!(t.with_timezone(&Utc), Utc.with_ymd_and_hms(1989, 11, 9, 21, 45, 0).unwrap());
+    }
+
+    #[test]
+    fn parse_datetime_rfc2822_test() {
+        let t = parse_datetime("Thu, 09 Nov 1989 22:45:00 +0100", None).unwrap();
+        assert_eq!(t.with_timezone(&Utc), Utc.with_ymd_and_hms(1989, 11, 9, 21, 45, 0).unwrap());
+    }
+
+    #[test]
+    fn parse_datetime_unix_test() {
+        let t = parse_datetime("626651100", None).unwrap();
+        assert_eq!(t.with_timezone(&Utc), Utc.timestamp_opt(626651100, 0).unwrap());
+    }
+
+    #[test]
+    fn parse_datetime_format_test() {
+        let t = parse_datetime("09/11/1989 22:45", Some("%d/%m/%Y %H:%M")).unwrap();
+        assert_eq!(t.naive_local(), Utc.with_ymd_and_hms(1989, 11, 9, 22, 45, 0).unwrap().naive_utc());
+    }
+
+    #[test]
+    fn parse_datetime_format_date_only_test() {
+        let t = parse_datetime("09/11/1989", Some("%d/%m/%Y")).unwrap();
+        assert_eq!(t.naive_local(), Utc.with_ymd_and_hms(1989, 11, 9, 0, 0, 0).unwrap().naive_utc());
+    }
+
+    #[test]
+    fn parse_datetime_failure_test() {
+        let errors = parse_datetime("not a date", None).unwrap_err();
+        assert_eq!(errors.len(), 4);
+    }
+
+    #[test]
+    fn parse_args_none_test() {
+        let parsed = parse_args(&[]);
+        assert!(parsed.datetime.is_none());
+        assert_eq!(parsed.locale, Locale::POSIX);
+        assert_eq!(parsed.custom_rows.unwrap(), Vec::new());
+    }
+
+    #[test]
+    fn parse_args_row_test() {
+        let args: Vec<String> = vec![String::from("--row"), String::from("Custom=%Y")];
+        let parsed = parse_args(&args);
+        assert_eq!(
+            parsed.custom_rows.unwrap(),
+            vec![(String::from("Custom"), String::from("%Y"))]
+        );
+    }
+
+    #[test]
+    fn parse_args_row_invalid_test() {
+        let args: Vec<String> = vec![String::from("--row"), String::from("Custom=%Q")];
+        let parsed = parse_args(&args);
+        assert!(parsed.custom_rows.is_err());
+    }
+
+    #[test]
+    fn make_combodate_table_custom_row_test() {
+        let test_date = Utc
+            .with_ymd_and_hms(1989, 11, 9, 21, 45, 0)
+            .unwrap()
+            .with_timezone(&Local);
+        let custom_rows = vec![(String::from("Year only"), String::from("%Y"))];
+        let table = make_combodate_table(test_date, Locale::POSIX, &custom_rows);
+        assert!(table.contains("Year only"));
+        assert!(table.contains("1989"));
+    }
+
+    #[test]
+    fn locale_thousands_sep_default_test() {
+        assert_eq!(locale_thousands_sep(Locale::POSIX), ' ');
+    }
+
+    #[test]
+    fn locale_weekday_name_test() {
+        let test_date = Utc.with_ymd_and_hms(1989, 11, 9, 22, 45, 0).unwrap();
+        assert_eq!(locale_weekday_name(test_date, Locale::POSIX), "Thursday");
+    }
+
+    #[test]
+    fn locale_month_name_test() {
+        let test_date = Utc.with_ymd_and_hms(1989, 11, 9, 22, 45, 0).unwrap();
+        assert_eq!(locale_month_name(test_date, Locale::POSIX), "November");
+    }
+
+    #[test]
+    fn doomsday_test_known_date() {
+        // 1989-11-09 was a Thursday.
+        assert_eq!(doomsday_weekday(1989, 11, 9), 4);
+    }
+
+    #[test]
+    fn doomsday_test_cross_check() {
+        for year in 1800..2200 {
+            for month in 1..=12u32 {
+                for day in [1, 13, month_length(year, month)] {
+                    let expected = Utc
+                        .with_ymd_and_hms(year, month, day, 0, 0, 0)
+                        .unwrap()
+                        .weekday()
+                        .num_days_from_sunday() as usize;
+                    assert_eq!(
+                        doomsday_weekday(year, month, day),
+                        expected,
+                        "{}-{}-{}",
+                        year,
+                        month,
+                        day
+                    );
+                }
+            }
+        }
+    }
 }